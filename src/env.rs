@@ -0,0 +1,74 @@
+//! Typed accessors for the environment variables Alfred injects into a script filter's process.
+//!
+//! Alfred invokes a workflow's script with context about the workflow (its bundle ID, data and
+//! cache directories, whether debug mode is on) set as environment variables, plus whatever
+//! variables a previous workflow step passed out via `Builder::variable` / `ItemBuilder::variable`.
+//! This module is the read side of that; see the `json` module for writing variables out.
+
+use std::env;
+use std::path::PathBuf;
+
+/// Returns the workflow's bundle identifier (`alfred_workflow_bundleid`).
+pub fn bundle_id() -> Option<String> {
+    variable("alfred_workflow_bundleid")
+}
+
+/// Returns the workflow's version, as set in the workflow's configuration
+/// (`alfred_workflow_version`).
+pub fn workflow_version() -> Option<String> {
+    variable("alfred_workflow_version")
+}
+
+/// Returns the directory Alfred has set aside for the workflow to store persistent data
+/// (`alfred_workflow_data`).
+pub fn workflow_data_dir() -> Option<PathBuf> {
+    env::var_os("alfred_workflow_data").map(PathBuf::from)
+}
+
+/// Returns the directory Alfred has set aside for the workflow to store cache data
+/// (`alfred_workflow_cache`).
+pub fn workflow_cache_dir() -> Option<PathBuf> {
+    env::var_os("alfred_workflow_cache").map(PathBuf::from)
+}
+
+/// Returns whether Alfred's debugger is open for this workflow (`alfred_debug`).
+pub fn is_debug() -> bool {
+    variable("alfred_debug").is_some_and(|v| v == "1")
+}
+
+/// Returns the value of the named environment variable, as set by Alfred or a previous workflow
+/// step's `Builder::variable` / `ItemBuilder::variable`.
+///
+/// This is the generic escape hatch for user-defined variables; see `bundle_id`,
+/// `workflow_data_dir`, `workflow_cache_dir`, and `is_debug` for Alfred's own variables.
+pub fn variable<K: AsRef<str>>(name: K) -> Option<String> {
+    env::var(name.as_ref()).ok()
+}
+
+#[test]
+fn test_env() {
+    env::remove_var("alfred_workflow_bundleid");
+    env::remove_var("alfred_workflow_version");
+    env::remove_var("alfred_workflow_data");
+    env::remove_var("alfred_workflow_cache");
+    env::remove_var("alfred_debug");
+    assert_eq!(bundle_id(), None);
+    assert_eq!(workflow_version(), None);
+    assert_eq!(workflow_data_dir(), None);
+    assert_eq!(workflow_cache_dir(), None);
+    assert!(!is_debug());
+
+    env::set_var("alfred_workflow_bundleid", "com.example.workflow");
+    env::set_var("alfred_workflow_version", "1.2.0");
+    env::set_var("alfred_workflow_data", "/data");
+    env::set_var("alfred_workflow_cache", "/cache");
+    env::set_var("alfred_debug", "1");
+    assert_eq!(bundle_id(), Some("com.example.workflow".to_string()));
+    assert_eq!(workflow_version(), Some("1.2.0".to_string()));
+    assert_eq!(workflow_data_dir(), Some(PathBuf::from("/data")));
+    assert_eq!(workflow_cache_dir(), Some(PathBuf::from("/cache")));
+    assert!(is_debug());
+
+    env::set_var("my_custom_var", "banana");
+    assert_eq!(variable("my_custom_var"), Some("banana".to_string()));
+}
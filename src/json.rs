@@ -82,6 +82,87 @@ pub fn write_items<W: Write>(w: W, items: &[Item]) -> io::Result<()> {
     Builder::with_items(items).write(w)
 }
 
+/// Incrementally writes a script filter JSON document, one `Item` at a time.
+///
+/// Unlike `Builder`, which collects every `Item` into a single `serde_json::Value` tree before
+/// writing it out, `JsonWriter` serializes and writes each item as soon as it's handed over.
+/// This avoids holding thousands of items (and their serialized `Value`s) in memory at once,
+/// which matters for script filters that stream a large number of results.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate alfred;
+/// # use std::io::{self, Write};
+/// #
+/// # fn write_items() -> io::Result<()> {
+/// let mut writer = alfred::json::JsonWriter::new(io::stdout())?;
+/// writer.write_item(&alfred::Item::new("Item 1"))?;
+/// writer.write_item(&alfred::ItemBuilder::new("Item 2")
+///                                        .subtitle("Subtitle")
+///                                        .into_item())?;
+/// writer.close()
+/// # }
+/// #
+/// # fn main() {
+/// #     match write_items() {
+/// #         Ok(()) => {},
+/// #         Err(err) => {
+/// #             let _ = writeln!(&mut io::stderr(), "Error writing items: {}", err);
+/// #         }
+/// #     }
+/// # }
+/// ```
+pub struct JsonWriter<W: Write> {
+    w: W,
+    wrote_item: bool,
+    variables: HashMap<String, String>,
+}
+
+impl<W: Write> JsonWriter<W> {
+    /// Creates a new `JsonWriter`, writing the opening `{"items":[` to `w`.
+    pub fn new(mut w: W) -> io::Result<JsonWriter<W>> {
+        write!(&mut w, "{{\"items\":[")?;
+        Ok(JsonWriter {
+            w,
+            wrote_item: false,
+            variables: HashMap::new(),
+        })
+    }
+
+    /// Inserts a top-level variable, to be written out when `close` is called.
+    ///
+    /// Top-level variables are accumulated here rather than written immediately, since they're
+    /// serialized after the `items` array and `close` is the only point at which that's known
+    /// to have finished.
+    pub fn variable<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        self.variables.insert(key.into(), value.into());
+    }
+
+    /// Serializes `item` and writes it to the underlying `Write`, preceded by a comma if it's
+    /// not the first item written.
+    pub fn write_item(&mut self, item: &Item) -> io::Result<()> {
+        if self.wrote_item {
+            write!(&mut self.w, ",")?;
+        }
+        self.wrote_item = true;
+        json::to_writer(&mut self.w, &item.to_json())?;
+        Ok(())
+    }
+
+    /// Writes any accumulated top-level variables and the closing `]}`, then flushes the
+    /// underlying `Write`.
+    pub fn close(mut self) -> io::Result<()> {
+        write!(&mut self.w, "]")?;
+        if !self.variables.is_empty() {
+            write!(&mut self.w, ",\"variables\":")?;
+            json::to_writer(&mut self.w, &self.variables)?;
+        }
+        write!(&mut self.w, "}}")?;
+        self.w.flush()
+    }
+}
+
 /// A helper type for writing out items with top-level variables.
 ///
 /// Note: If you don't need top-level variables the `write_items()` function is easier to use.
@@ -91,14 +172,25 @@ pub struct Builder<'a> {
     pub items: &'a [Item<'a>],
     /// The variables that will be written out.
     pub variables: HashMap<&'a str, &'a str>,
+    /// The number of seconds to wait before Alfred re-invokes the script filter, if set.
+    pub rerun: Option<f64>,
+    /// Whether Alfred's own knowledge-based sorting of results should be skipped.
+    pub skip_knowledge: bool,
 }
 
+/// The minimum number of seconds Alfred will wait before re-invoking the script filter.
+const RERUN_MIN: f64 = 0.1;
+/// The maximum number of seconds Alfred will wait before re-invoking the script filter.
+const RERUN_MAX: f64 = 5.0;
+
 impl<'a> Builder<'a> {
     /// Returns a new `Builder` with no items.
     pub fn new() -> Builder<'a> {
         Builder {
             items: &[],
             variables: HashMap::new(),
+            rerun: None,
+            skip_knowledge: false,
         }
     }
 
@@ -107,6 +199,8 @@ impl<'a> Builder<'a> {
         Builder {
             items,
             variables: HashMap::new(),
+            rerun: None,
+            skip_knowledge: false,
         }
     }
 
@@ -134,6 +228,15 @@ impl<'a> Builder<'a> {
             }
             root.insert("variables".to_owned(), Value::Object(vars));
         }
+        if let Some(rerun) = self.rerun {
+            // `rerun` is a public field and can be set directly (bypassing the clamp in
+            // `set_rerun`), so clamp again here to guarantee we never serialize a value outside
+            // Alfred's accepted 0.1-5.0 range.
+            root.insert("rerun".to_owned(), json!(rerun.clamp(RERUN_MIN, RERUN_MAX)));
+        }
+        if self.skip_knowledge {
+            root.insert("skipknowledge".to_owned(), Value::Bool(true));
+        }
         Value::Object(root)
     }
 
@@ -155,6 +258,20 @@ impl<'a> Builder<'a> {
         self
     }
 
+    /// Sets the number of seconds Alfred should wait before re-invoking the script filter.
+    ///
+    /// `seconds` is clamped to Alfred's accepted range of 0.1 to 5.0.
+    pub fn rerun(mut self, seconds: f64) -> Builder<'a> {
+        self.set_rerun(seconds);
+        self
+    }
+
+    /// Sets whether Alfred's own knowledge-based sorting of results should be skipped.
+    pub fn skip_knowledge(mut self, skip_knowledge: bool) -> Builder<'a> {
+        self.set_skip_knowledge(skip_knowledge);
+        self
+    }
+
     /// Replaces the builder's items with `items`.
     pub fn set_items(&mut self, items: &'a [Item]) {
         self.items = items
@@ -169,6 +286,45 @@ impl<'a> Builder<'a> {
     pub fn set_variable(&mut self, key: &'a str, value: &'a str) {
         self.variables.insert(key, value);
     }
+
+    /// Sets the number of seconds Alfred should wait before re-invoking the script filter.
+    ///
+    /// `seconds` is clamped to Alfred's accepted range of 0.1 to 5.0.
+    pub fn set_rerun(&mut self, seconds: f64) {
+        self.rerun = Some(seconds.clamp(RERUN_MIN, RERUN_MAX));
+    }
+
+    /// Sets whether Alfred's own knowledge-based sorting of results should be skipped.
+    pub fn set_skip_knowledge(&mut self, skip_knowledge: bool) {
+        self.skip_knowledge = skip_knowledge;
+    }
+
+    /// Parses the `items` and top-level `variables` out of a script filter JSON document of the
+    /// shape produced by `into_json`.
+    ///
+    /// Since a `Builder` borrows its items, this returns owned `Item`s rather than a `Builder`
+    /// directly; pass the result to `Builder::with_items` if a borrowing `Builder` is needed.
+    ///
+    /// Returns `None` if `value` isn't a JSON object with an `items` array.
+    pub fn from_json(value: &Value) -> Option<(Vec<Item<'static>>, HashMap<String, String>)> {
+        let obj = value.as_object()?;
+        let items = obj
+            .get("items")?
+            .as_array()?
+            .iter()
+            .filter_map(Item::from_json)
+            .collect();
+        let variables = obj
+            .get("variables")
+            .and_then(Value::as_object)
+            .map(|vars| {
+                vars.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some((items, variables))
+    }
 }
 
 impl<'a> Item<'a> {
@@ -203,6 +359,9 @@ impl<'a> Item<'a> {
         if let Some(ref autocomplete) = self.autocomplete {
             d.insert("autocomplete".to_string(), json!(autocomplete));
         }
+        if let Some(ref match_) = self.match_ {
+            d.insert("match".to_string(), json!(match_));
+        }
         if self.text_copy.is_some() || self.text_large_type.is_some() {
             let mut text = json::Map::new();
             if let Some(ref text_copy) = self.text_copy {
@@ -242,6 +401,76 @@ impl<'a> Item<'a> {
     }
 }
 
+impl Item<'static> {
+    /// Parses an `Item` from the JSON representation produced by `to_json`.
+    ///
+    /// Returns `None` if `value` isn't a JSON object with a string `title`.
+    pub fn from_json(value: &Value) -> Option<Item<'static>> {
+        let obj = value.as_object()?;
+        let title = obj.get("title")?.as_str()?.to_string();
+        let mut item = Item::new(title);
+        if let Some(subtitle) = obj.get("subtitle").and_then(Value::as_str) {
+            item.subtitle = Some(subtitle.to_string().into());
+        }
+        if let Some(icon) = obj.get("icon") {
+            item.icon = Icon::from_json(icon);
+        }
+        if let Some(uid) = obj.get("uid").and_then(Value::as_str) {
+            item.uid = Some(uid.to_string().into());
+        }
+        if let Some(arg) = obj.get("arg").and_then(Value::as_str) {
+            item.arg = Some(arg.to_string().into());
+        }
+        item.type_ = match obj.get("type").and_then(Value::as_str) {
+            Some("file") => ItemType::File,
+            Some("file:skipcheck") => ItemType::FileSkipCheck,
+            _ => ItemType::Default,
+        };
+        item.valid = obj.get("valid").and_then(Value::as_bool).unwrap_or(true);
+        if let Some(autocomplete) = obj.get("autocomplete").and_then(Value::as_str) {
+            item.autocomplete = Some(autocomplete.to_string().into());
+        }
+        if let Some(match_) = obj.get("match").and_then(Value::as_str) {
+            item.match_ = Some(match_.to_string().into());
+        }
+        if let Some(text) = obj.get("text").and_then(Value::as_object) {
+            if let Some(copy) = text.get("copy").and_then(Value::as_str) {
+                item.text_copy = Some(copy.to_string().into());
+            }
+            if let Some(large_type) = text.get("largetype").and_then(Value::as_str) {
+                item.text_large_type = Some(large_type.to_string().into());
+            }
+        }
+        if let Some(url) = obj.get("quicklookurl").and_then(Value::as_str) {
+            item.quicklook_url = Some(url.to_string().into());
+        }
+        if let Some(mods) = obj.get("mods").and_then(Value::as_object) {
+            for (key, data) in mods {
+                let modifier = match key.as_str() {
+                    "cmd" => Modifier::Command,
+                    "alt" => Modifier::Option,
+                    "ctrl" => Modifier::Control,
+                    "shift" => Modifier::Shift,
+                    "fn" => Modifier::Fn,
+                    _ => continue,
+                };
+                if let Some(data) = ModifierData::from_json(data) {
+                    item.modifiers.insert(modifier, data);
+                }
+            }
+        }
+        if let Some(variables) = obj.get("variables").and_then(Value::as_object) {
+            for (key, value) in variables {
+                if let Some(value) = value.as_str() {
+                    item.variables
+                        .insert(key.clone().into(), value.to_string().into());
+                }
+            }
+        }
+        Some(item)
+    }
+}
+
 impl<'a> Icon<'a> {
     /// Serializes the `Icon` into its JSON representation.
     pub fn to_json(&self) -> Value {
@@ -253,6 +482,21 @@ impl<'a> Icon<'a> {
     }
 }
 
+impl Icon<'static> {
+    /// Parses an `Icon` from the JSON representation produced by `to_json`.
+    ///
+    /// Returns `None` if `value` isn't a JSON object with a string `path`.
+    pub fn from_json(value: &Value) -> Option<Icon<'static>> {
+        let obj = value.as_object()?;
+        let path = obj.get("path")?.as_str()?.to_string();
+        match obj.get("type").and_then(Value::as_str) {
+            Some("fileicon") => Some(Icon::File(path.into())),
+            Some("filetype") => Some(Icon::FileType(path.into())),
+            _ => Some(Icon::Path(path.into())),
+        }
+    }
+}
+
 impl<'a> ModifierData<'a> {
     /// Serializes the `ModifierData` into its JSON representation.
     pub fn to_json(&self) -> Value {
@@ -280,6 +524,37 @@ impl<'a> ModifierData<'a> {
     }
 }
 
+impl ModifierData<'static> {
+    /// Parses a `ModifierData` from the JSON representation produced by `to_json`.
+    ///
+    /// Returns `None` if `value` isn't a JSON object.
+    pub fn from_json(value: &Value) -> Option<ModifierData<'static>> {
+        let obj = value.as_object()?;
+        let mut data = ModifierData::new();
+        if let Some(subtitle) = obj.get("subtitle").and_then(Value::as_str) {
+            data.subtitle = Some(subtitle.to_string().into());
+        }
+        if let Some(arg) = obj.get("arg").and_then(Value::as_str) {
+            data.arg = Some(arg.to_string().into());
+        }
+        if let Some(valid) = obj.get("valid").and_then(Value::as_bool) {
+            data.valid = Some(valid);
+        }
+        if let Some(icon) = obj.get("icon") {
+            data.icon = Icon::from_json(icon);
+        }
+        if let Some(variables) = obj.get("variables").and_then(Value::as_object) {
+            for (key, value) in variables {
+                if let Some(value) = value.as_str() {
+                    data.variables
+                        .insert(key.clone().into(), value.to_string().into());
+                }
+            }
+        }
+        Some(data)
+    }
+}
+
 #[test]
 fn test_to_json() {
     let item = Item::new("Item 1");
@@ -380,6 +655,45 @@ fn test_to_json() {
             }
         })
     );
+    let item = ::ItemBuilder::new("Item 7")
+        .match_("item seven alias")
+        .into_item();
+    assert_eq!(
+        item.to_json(),
+        json!({
+            "title": "Item 7",
+            "match": "item seven alias"
+        })
+    );
+}
+
+#[test]
+fn test_json_writer() {
+    let mut buf = Vec::new();
+    {
+        let mut writer = JsonWriter::new(&mut buf).unwrap();
+        writer.write_item(&Item::new("Item 1")).unwrap();
+        writer
+            .write_item(
+                &::ItemBuilder::new("Item 2")
+                    .subtitle("Subtitle")
+                    .into_item(),
+            )
+            .unwrap();
+        writer.variable("fruit", "banana");
+        writer.close().unwrap();
+    }
+    let value: Value = json::from_slice(&buf).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "items": [
+                {"title": "Item 1"},
+                {"title": "Item 2", "subtitle": "Subtitle"}
+            ],
+            "variables": {"fruit": "banana"}
+        })
+    );
 }
 
 #[test]
@@ -423,3 +737,84 @@ fn test_builder() {
         })
     );
 }
+
+#[test]
+fn test_builder_rerun_and_skip_knowledge() {
+    let json = Builder::with_items(&[Item::new("Item 1")])
+        .rerun(1.5)
+        .skip_knowledge(true)
+        .into_json();
+    assert_eq!(
+        json,
+        json!({
+            "items": [
+                {
+                    "title": "Item 1"
+                }
+            ],
+            "rerun": 1.5,
+            "skipknowledge": true
+        })
+    );
+
+    let json = Builder::with_items(&[Item::new("Item 1")])
+        .rerun(10.0)
+        .into_json();
+    assert_eq!(json["rerun"], json!(5.0));
+
+    let json = Builder::with_items(&[Item::new("Item 1")])
+        .rerun(0.0)
+        .into_json();
+    assert_eq!(json["rerun"], json!(0.1));
+
+    let mut builder = Builder::with_items(&[Item::new("Item 1")]);
+    builder.rerun = Some(99.0);
+    assert_eq!(builder.into_json()["rerun"], json!(5.0));
+}
+
+#[test]
+fn test_item_round_trip() {
+    let item = ::ItemBuilder::new("Item 4")
+        .arg("Argument")
+        .subtitle("Subtitle")
+        .uid("item-4")
+        .autocomplete("Item Four")
+        .match_("four 4")
+        .text_copy("copy text")
+        .text_large_type("large type text")
+        .quicklook_url("https://example.com")
+        .arg_mod(Modifier::Option, "Alt Argument")
+        .valid_mod(Modifier::Option, false)
+        .icon_file_mod(Modifier::Option, "opt.png")
+        .variable("fruit", "banana")
+        .variable_mod(Modifier::Option, "vegetable", "carrot")
+        .into_item();
+    let round_tripped = Item::from_json(&item.to_json()).unwrap();
+    assert_eq!(item.to_json(), round_tripped.to_json());
+}
+
+#[test]
+fn test_builder_round_trip() {
+    let items = [
+        Item::new("Item 1"),
+        ::ItemBuilder::new("Item 2")
+            .subtitle("Subtitle")
+            .icon_filetype("public.folder")
+            .into_item(),
+    ];
+    let json = Builder::with_items(&items)
+        .variable("fruit", "banana")
+        .into_json();
+    let (round_tripped_items, round_tripped_variables) = Builder::from_json(&json).unwrap();
+    assert_eq!(
+        round_tripped_items
+            .iter()
+            .map(Item::to_json)
+            .collect::<Vec<_>>(),
+        items.iter().map(Item::to_json).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        round_tripped_variables.get("fruit").map(String::as_str),
+        Some("banana")
+    );
+}
@@ -0,0 +1,431 @@
+//! A crate for working with [Alfred's](http://www.alfredapp.com/) `script filter` JSON
+//! format ([docs](https://www.alfredapp.com/help/workflows/inputs/script-filter/json/)).
+//!
+//! See the `json` module for functions to write out a set of `Item`s, and the `env` module for
+//! reading the variables Alfred passes into the workflow's environment.
+
+#[macro_use]
+extern crate serde_json;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+pub mod env;
+pub mod json;
+
+/// An item for a script filter.
+///
+/// Use `ItemBuilder` to construct an `Item`, or construct one directly if only a title is
+/// needed.
+#[derive(Clone, Debug)]
+pub struct Item<'a> {
+    /// The title of the item.
+    pub title: Cow<'a, str>,
+    /// The subtitle of the item.
+    pub subtitle: Option<Cow<'a, str>>,
+    /// The icon for the item.
+    pub icon: Option<Icon<'a>>,
+    /// An identifier used by Alfred to learn about this item for sorting and
+    /// suggesting purposes.
+    pub uid: Option<Cow<'a, str>>,
+    /// The argument that is passed through the workflow to the next action.
+    pub arg: Option<Cow<'a, str>>,
+    /// The type of the item.
+    pub type_: ItemType,
+    /// Whether or not the result is "valid", in that it can be actioned.
+    pub valid: bool,
+    /// An optional string to override the `title` when the auto-complete feature (the user
+    /// pressing Tab) is used.
+    pub autocomplete: Option<Cow<'a, str>>,
+    /// Text Alfred uses for its typed-filtering instead of the `title`, letting the item expose
+    /// search keywords (aliases, abbreviations, tokenized forms) that differ from what's shown.
+    pub match_: Option<Cow<'a, str>>,
+    /// The text the user gets when copying the result (CMD+C).
+    pub text_copy: Option<Cow<'a, str>>,
+    /// The text the user gets when displaying large type (CMD+L).
+    pub text_large_type: Option<Cow<'a, str>>,
+    /// A URL to be displayed with the built-in Quick Look (tapping shift, or CMD+Y).
+    pub quicklook_url: Option<Cow<'a, str>>,
+    /// Per-modifier-key overrides for this item.
+    pub modifiers: HashMap<Modifier, ModifierData<'a>>,
+    /// Variables to pass out of the workflow if this item is actioned.
+    pub variables: HashMap<Cow<'a, str>, Cow<'a, str>>,
+}
+
+impl<'a> Item<'a> {
+    /// Returns a new `Item` with the given title, and all other fields set to their defaults.
+    pub fn new<S: Into<Cow<'a, str>>>(title: S) -> Item<'a> {
+        Item {
+            title: title.into(),
+            subtitle: None,
+            icon: None,
+            uid: None,
+            arg: None,
+            type_: ItemType::Default,
+            valid: true,
+            autocomplete: None,
+            match_: None,
+            text_copy: None,
+            text_large_type: None,
+            quicklook_url: None,
+            modifiers: HashMap::new(),
+            variables: HashMap::new(),
+        }
+    }
+}
+
+/// A builder used to construct `Item`s.
+#[derive(Clone, Debug)]
+pub struct ItemBuilder<'a> {
+    item: Item<'a>,
+}
+
+impl<'a> ItemBuilder<'a> {
+    /// Returns a new `ItemBuilder` with the given title, and all other fields set to their
+    /// defaults.
+    pub fn new<S: Into<Cow<'a, str>>>(title: S) -> ItemBuilder<'a> {
+        ItemBuilder {
+            item: Item::new(title),
+        }
+    }
+
+    /// Returns a new `ItemBuilder` using the given `Item` as a starting point.
+    pub fn with_item(item: Item<'a>) -> ItemBuilder<'a> {
+        ItemBuilder { item }
+    }
+
+    /// Consumes the builder and returns the built up `Item`.
+    pub fn into_item(self) -> Item<'a> {
+        self.item
+    }
+
+    /// Sets the subtitle.
+    pub fn subtitle<S: Into<Cow<'a, str>>>(mut self, subtitle: S) -> ItemBuilder<'a> {
+        self.set_subtitle(subtitle);
+        self
+    }
+
+    /// Sets the subtitle for a modifier key.
+    pub fn subtitle_mod<S: Into<Cow<'a, str>>>(
+        mut self,
+        modifier: Modifier,
+        subtitle: S,
+    ) -> ItemBuilder<'a> {
+        self.set_subtitle_mod(modifier, subtitle);
+        self
+    }
+
+    /// Sets the icon to a path on disk.
+    pub fn icon_path<S: Into<Cow<'a, str>>>(mut self, path: S) -> ItemBuilder<'a> {
+        self.set_icon_path(path);
+        self
+    }
+
+    /// Sets the icon for a modifier key to a path on disk.
+    pub fn icon_path_mod<S: Into<Cow<'a, str>>>(
+        mut self,
+        modifier: Modifier,
+        path: S,
+    ) -> ItemBuilder<'a> {
+        self.set_icon_path_mod(modifier, path);
+        self
+    }
+
+    /// Sets the icon to the icon of the file at the given path.
+    pub fn icon_file<S: Into<Cow<'a, str>>>(mut self, path: S) -> ItemBuilder<'a> {
+        self.set_icon_file(path);
+        self
+    }
+
+    /// Sets the icon for a modifier key to the icon of the file at the given path.
+    pub fn icon_file_mod<S: Into<Cow<'a, str>>>(
+        mut self,
+        modifier: Modifier,
+        path: S,
+    ) -> ItemBuilder<'a> {
+        self.set_icon_file_mod(modifier, path);
+        self
+    }
+
+    /// Sets the icon to the icon for the given UTI (uniform type identifier).
+    pub fn icon_filetype<S: Into<Cow<'a, str>>>(mut self, filetype: S) -> ItemBuilder<'a> {
+        self.set_icon_filetype(filetype);
+        self
+    }
+
+    /// Sets the icon for a modifier key to the icon for the given UTI.
+    pub fn icon_filetype_mod<S: Into<Cow<'a, str>>>(
+        mut self,
+        modifier: Modifier,
+        filetype: S,
+    ) -> ItemBuilder<'a> {
+        self.set_icon_filetype_mod(modifier, filetype);
+        self
+    }
+
+    /// Sets the identifier Alfred uses to learn about the item.
+    pub fn uid<S: Into<Cow<'a, str>>>(mut self, uid: S) -> ItemBuilder<'a> {
+        self.set_uid(uid);
+        self
+    }
+
+    /// Sets the argument passed out of the workflow.
+    pub fn arg<S: Into<Cow<'a, str>>>(mut self, arg: S) -> ItemBuilder<'a> {
+        self.set_arg(arg);
+        self
+    }
+
+    /// Sets the argument for a modifier key.
+    pub fn arg_mod<S: Into<Cow<'a, str>>>(mut self, modifier: Modifier, arg: S) -> ItemBuilder<'a> {
+        self.set_arg_mod(modifier, arg);
+        self
+    }
+
+    /// Sets the type of the item.
+    pub fn type_(mut self, type_: ItemType) -> ItemBuilder<'a> {
+        self.set_type(type_);
+        self
+    }
+
+    /// Sets whether or not the item is valid.
+    pub fn valid(mut self, valid: bool) -> ItemBuilder<'a> {
+        self.set_valid(valid);
+        self
+    }
+
+    /// Sets whether or not the item is valid for a modifier key.
+    pub fn valid_mod(mut self, modifier: Modifier, valid: bool) -> ItemBuilder<'a> {
+        self.set_valid_mod(modifier, valid);
+        self
+    }
+
+    /// Sets the autocomplete text.
+    pub fn autocomplete<S: Into<Cow<'a, str>>>(mut self, autocomplete: S) -> ItemBuilder<'a> {
+        self.set_autocomplete(autocomplete);
+        self
+    }
+
+    /// Sets the text Alfred uses for its typed-filtering instead of the title.
+    pub fn match_<S: Into<Cow<'a, str>>>(mut self, match_: S) -> ItemBuilder<'a> {
+        self.set_match_(match_);
+        self
+    }
+
+    /// Sets the text shown when the item is copied with CMD+C.
+    pub fn text_copy<S: Into<Cow<'a, str>>>(mut self, text_copy: S) -> ItemBuilder<'a> {
+        self.set_text_copy(text_copy);
+        self
+    }
+
+    /// Sets the text shown when displaying large type with CMD+L.
+    pub fn text_large_type<S: Into<Cow<'a, str>>>(mut self, text_large_type: S) -> ItemBuilder<'a> {
+        self.set_text_large_type(text_large_type);
+        self
+    }
+
+    /// Sets the URL shown with Quick Look.
+    pub fn quicklook_url<S: Into<Cow<'a, str>>>(mut self, url: S) -> ItemBuilder<'a> {
+        self.set_quicklook_url(url);
+        self
+    }
+
+    /// Inserts a variable to be passed out of the workflow if this item is actioned.
+    pub fn variable<K: Into<Cow<'a, str>>, V: Into<Cow<'a, str>>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> ItemBuilder<'a> {
+        self.set_variable(key, value);
+        self
+    }
+
+    /// Inserts a variable to be passed out of the workflow if this item is actioned with a
+    /// modifier key held down.
+    pub fn variable_mod<K: Into<Cow<'a, str>>, V: Into<Cow<'a, str>>>(
+        mut self,
+        modifier: Modifier,
+        key: K,
+        value: V,
+    ) -> ItemBuilder<'a> {
+        self.set_variable_mod(modifier, key, value);
+        self
+    }
+
+    /// Sets the subtitle.
+    pub fn set_subtitle<S: Into<Cow<'a, str>>>(&mut self, subtitle: S) {
+        self.item.subtitle = Some(subtitle.into());
+    }
+
+    /// Sets the subtitle for a modifier key.
+    pub fn set_subtitle_mod<S: Into<Cow<'a, str>>>(&mut self, modifier: Modifier, subtitle: S) {
+        self.modifier_data(modifier).subtitle = Some(subtitle.into());
+    }
+
+    /// Sets the icon to a path on disk.
+    pub fn set_icon_path<S: Into<Cow<'a, str>>>(&mut self, path: S) {
+        self.item.icon = Some(Icon::Path(path.into()));
+    }
+
+    /// Sets the icon for a modifier key to a path on disk.
+    pub fn set_icon_path_mod<S: Into<Cow<'a, str>>>(&mut self, modifier: Modifier, path: S) {
+        self.modifier_data(modifier).icon = Some(Icon::Path(path.into()));
+    }
+
+    /// Sets the icon to the icon of the file at the given path.
+    pub fn set_icon_file<S: Into<Cow<'a, str>>>(&mut self, path: S) {
+        self.item.icon = Some(Icon::File(path.into()));
+    }
+
+    /// Sets the icon for a modifier key to the icon of the file at the given path.
+    pub fn set_icon_file_mod<S: Into<Cow<'a, str>>>(&mut self, modifier: Modifier, path: S) {
+        self.modifier_data(modifier).icon = Some(Icon::File(path.into()));
+    }
+
+    /// Sets the icon to the icon for the given UTI (uniform type identifier).
+    pub fn set_icon_filetype<S: Into<Cow<'a, str>>>(&mut self, filetype: S) {
+        self.item.icon = Some(Icon::FileType(filetype.into()));
+    }
+
+    /// Sets the icon for a modifier key to the icon for the given UTI.
+    pub fn set_icon_filetype_mod<S: Into<Cow<'a, str>>>(&mut self, modifier: Modifier, filetype: S) {
+        self.modifier_data(modifier).icon = Some(Icon::FileType(filetype.into()));
+    }
+
+    /// Sets the identifier Alfred uses to learn about the item.
+    pub fn set_uid<S: Into<Cow<'a, str>>>(&mut self, uid: S) {
+        self.item.uid = Some(uid.into());
+    }
+
+    /// Sets the argument passed out of the workflow.
+    pub fn set_arg<S: Into<Cow<'a, str>>>(&mut self, arg: S) {
+        self.item.arg = Some(arg.into());
+    }
+
+    /// Sets the argument for a modifier key.
+    pub fn set_arg_mod<S: Into<Cow<'a, str>>>(&mut self, modifier: Modifier, arg: S) {
+        self.modifier_data(modifier).arg = Some(arg.into());
+    }
+
+    /// Sets the type of the item.
+    pub fn set_type(&mut self, type_: ItemType) {
+        self.item.type_ = type_;
+    }
+
+    /// Sets whether or not the item is valid.
+    pub fn set_valid(&mut self, valid: bool) {
+        self.item.valid = valid;
+    }
+
+    /// Sets whether or not the item is valid for a modifier key.
+    pub fn set_valid_mod(&mut self, modifier: Modifier, valid: bool) {
+        self.modifier_data(modifier).valid = Some(valid);
+    }
+
+    /// Sets the autocomplete text.
+    pub fn set_autocomplete<S: Into<Cow<'a, str>>>(&mut self, autocomplete: S) {
+        self.item.autocomplete = Some(autocomplete.into());
+    }
+
+    /// Sets the text Alfred uses for its typed-filtering instead of the title.
+    pub fn set_match_<S: Into<Cow<'a, str>>>(&mut self, match_: S) {
+        self.item.match_ = Some(match_.into());
+    }
+
+    /// Sets the text shown when the item is copied with CMD+C.
+    pub fn set_text_copy<S: Into<Cow<'a, str>>>(&mut self, text_copy: S) {
+        self.item.text_copy = Some(text_copy.into());
+    }
+
+    /// Sets the text shown when displaying large type with CMD+L.
+    pub fn set_text_large_type<S: Into<Cow<'a, str>>>(&mut self, text_large_type: S) {
+        self.item.text_large_type = Some(text_large_type.into());
+    }
+
+    /// Sets the URL shown with Quick Look.
+    pub fn set_quicklook_url<S: Into<Cow<'a, str>>>(&mut self, url: S) {
+        self.item.quicklook_url = Some(url.into());
+    }
+
+    /// Inserts a variable to be passed out of the workflow if this item is actioned.
+    pub fn set_variable<K: Into<Cow<'a, str>>, V: Into<Cow<'a, str>>>(&mut self, key: K, value: V) {
+        self.item.variables.insert(key.into(), value.into());
+    }
+
+    /// Inserts a variable to be passed out of the workflow if this item is actioned with a
+    /// modifier key held down.
+    pub fn set_variable_mod<K: Into<Cow<'a, str>>, V: Into<Cow<'a, str>>>(
+        &mut self,
+        modifier: Modifier,
+        key: K,
+        value: V,
+    ) {
+        self.modifier_data(modifier)
+            .variables
+            .insert(key.into(), value.into());
+    }
+
+    fn modifier_data(&mut self, modifier: Modifier) -> &mut ModifierData<'a> {
+        self.item.modifiers.entry(modifier).or_default()
+    }
+}
+
+/// The type of an `Item`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ItemType {
+    /// A generic item.
+    #[default]
+    Default,
+    /// A file on disk. Alfred will check that the file exists at `arg`, and if it does, Alfred
+    /// will allow the user access to the file's actions (via `shift` or `CMD+click`).
+    File,
+    /// A file on disk. Unlike `File`, Alfred will not check that the file exists.
+    FileSkipCheck,
+}
+
+/// The icon for an `Item`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Icon<'a> {
+    /// Path to an image on disk.
+    Path(Cow<'a, str>),
+    /// Path to a file whose icon will be used.
+    File(Cow<'a, str>),
+    /// A uniform type identifier (UTI) whose icon will be used.
+    FileType(Cow<'a, str>),
+}
+
+/// A modifier key that can be held down to alter an `Item`'s behavior.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Modifier {
+    /// The command key.
+    Command,
+    /// The option (alt) key.
+    Option,
+    /// The control key.
+    Control,
+    /// The shift key.
+    Shift,
+    /// The fn key.
+    Fn,
+}
+
+/// Overrides applied to an `Item` when a `Modifier` key is held down.
+#[derive(Clone, Debug, Default)]
+pub struct ModifierData<'a> {
+    /// The subtitle to show instead of the item's subtitle.
+    pub subtitle: Option<Cow<'a, str>>,
+    /// The argument to pass out of the workflow instead of the item's argument.
+    pub arg: Option<Cow<'a, str>>,
+    /// Whether or not the item is valid.
+    pub valid: Option<bool>,
+    /// The icon to show instead of the item's icon.
+    pub icon: Option<Icon<'a>>,
+    /// Variables to pass out of the workflow instead of the item's variables.
+    pub variables: HashMap<Cow<'a, str>, Cow<'a, str>>,
+}
+
+impl<'a> ModifierData<'a> {
+    /// Returns a new, empty `ModifierData`.
+    pub fn new() -> ModifierData<'a> {
+        ModifierData::default()
+    }
+}